@@ -0,0 +1,110 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use crate::TrackErr;
+
+/// Runtime-tunable settings, loaded from the config file at startup and
+/// hot-reloaded by `inputs::ConfigWatcher` whenever that file changes on
+/// disk, so the tick interval or log rotation count can be changed without
+/// restarting the tracker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub tick_delay_secs: u64,
+    pub log_limit: usize,
+    pub log_base_name: String,
+    /// Ordered glob include/exclude rules, matched against window class,
+    /// title, and workspace name. Empty means log everything.
+    pub filters: Vec<FilterRule>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            tick_delay_secs: 10,
+            log_limit: 10,
+            log_base_name: "i3tracker".to_string(),
+            filters: Vec::new(),
+        }
+    }
+}
+
+/// One rule in a `Config::filters` list: a glob `pattern`, and whether a
+/// match excludes (the default) or re-includes a previously excluded
+/// event. Rules are evaluated in order and the last match wins, so a later
+/// `exclude = false` rule can carve an exception back out of an earlier
+/// exclude.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterRule {
+    pub pattern: String,
+    #[serde(default = "default_exclude")]
+    pub exclude: bool,
+}
+
+fn default_exclude() -> bool {
+    true
+}
+
+impl Config {
+    pub fn tick_delay(&self) -> Duration {
+        Duration::from_secs(self.tick_delay_secs)
+    }
+
+    /// The path of the config file under the XDG config dir, creating the
+    /// directory (but not the file) if necessary.
+    pub fn path() -> Result<PathBuf, TrackErr> {
+        let xdg_dir = xdg::BaseDirectories::with_prefix(crate::APP_NAME)?;
+        Ok(xdg_dir.place_config_file("config.toml")?)
+    }
+
+    /// Load the config from `path`, falling back to `Config::default()` if
+    /// the file doesn't exist yet.
+    pub fn load(path: &PathBuf) -> Result<Self, TrackErr> {
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let contents = fs::read_to_string(path)?;
+        toml::from_str(&contents).map_err(TrackErr::from)
+    }
+
+    /// Write the default config to `path` if nothing is there yet.
+    ///
+    /// `inputs::ConfigWatcher` calls this before watching, so a fresh
+    /// install (no `config.toml` yet) starts with a file holding the
+    /// defaults for the user to edit.
+    pub fn ensure_exists(path: &PathBuf) -> Result<(), TrackErr> {
+        if path.exists() {
+            return Ok(());
+        }
+        let toml = toml::to_string_pretty(&Config::default())
+            .expect("default config always serializes");
+        fs::write(path, toml)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_toml_fills_in_defaults() {
+        let config: Config = toml::from_str("tick_delay_secs = 30").unwrap();
+        assert_eq!(config.tick_delay_secs, 30);
+        assert_eq!(config.log_limit, Config::default().log_limit);
+        assert_eq!(config.log_base_name, Config::default().log_base_name);
+        assert!(config.filters.is_empty());
+    }
+
+    #[test]
+    fn filter_rule_exclude_defaults_to_true() {
+        let config: Config = toml::from_str(
+            r#"
+            [[filters]]
+            pattern = "KeePassXC"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.filters.len(), 1);
+        assert!(config.filters[0].exclude);
+    }
+}
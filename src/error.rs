@@ -0,0 +1,55 @@
+use std::{fmt, io, time::SystemTimeError};
+
+/// Errors that can occur while setting up or running the tracker.
+#[derive(Debug)]
+pub enum TrackErr {
+    Io(io::Error),
+    Xdg(xdg::BaseDirectoriesError),
+    Time(SystemTimeError),
+    Toml(toml::de::Error),
+    Glob(globset::Error),
+}
+
+impl fmt::Display for TrackErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrackErr::Io(e) => write!(f, "io error: {}", e),
+            TrackErr::Xdg(e) => write!(f, "xdg error: {}", e),
+            TrackErr::Time(e) => write!(f, "system time error: {}", e),
+            TrackErr::Toml(e) => write!(f, "config parse error: {}", e),
+            TrackErr::Glob(e) => write!(f, "filter pattern error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TrackErr {}
+
+impl From<io::Error> for TrackErr {
+    fn from(e: io::Error) -> Self {
+        TrackErr::Io(e)
+    }
+}
+
+impl From<xdg::BaseDirectoriesError> for TrackErr {
+    fn from(e: xdg::BaseDirectoriesError) -> Self {
+        TrackErr::Xdg(e)
+    }
+}
+
+impl From<SystemTimeError> for TrackErr {
+    fn from(e: SystemTimeError) -> Self {
+        TrackErr::Time(e)
+    }
+}
+
+impl From<toml::de::Error> for TrackErr {
+    fn from(e: toml::de::Error) -> Self {
+        TrackErr::Toml(e)
+    }
+}
+
+impl From<globset::Error> for TrackErr {
+    fn from(e: globset::Error) -> Self {
+        TrackErr::Glob(e)
+    }
+}
@@ -0,0 +1,44 @@
+use futures::{
+    sync::mpsc::{SendError, Sender},
+    Async, Sink, StartSend,
+};
+
+use crate::{i3log::Event, priority::Priority};
+
+/// Wraps the urgent and normal channels and routes each `Event` by its
+/// `Event::priority()`, so the priority scheme from the request is
+/// actually enforced in one place instead of relying on every `Input`
+/// picking the right `Sender` by hand.
+#[derive(Clone)]
+pub struct EventTx {
+    urgent: Sender<Event>,
+    normal: Sender<Event>,
+}
+
+impl EventTx {
+    pub fn new(urgent: Sender<Event>, normal: Sender<Event>) -> Self {
+        EventTx { urgent, normal }
+    }
+
+    fn channel(&mut self, priority: Priority) -> &mut Sender<Event> {
+        match priority {
+            Priority::Urgent => &mut self.urgent,
+            Priority::Normal => &mut self.normal,
+        }
+    }
+}
+
+impl Sink for EventTx {
+    type SinkItem = Event;
+    type SinkError = SendError<Event>;
+
+    fn start_send(&mut self, item: Event) -> StartSend<Event, Self::SinkError> {
+        let priority = item.priority();
+        self.channel(priority).start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Result<Async<()>, Self::SinkError> {
+        self.urgent.poll_complete()?;
+        self.normal.poll_complete()
+    }
+}
@@ -0,0 +1,99 @@
+use globset::{Glob, GlobMatcher};
+
+use crate::{config::FilterRule, i3log::I3Log, TrackErr};
+
+/// Ordered glob-based include/exclude rules, evaluated against a window's
+/// class, title, and workspace name before it reaches the write path in
+/// the consumer loop.
+///
+/// Modeled on watchexec's `GlobsetFilterer`: rules are evaluated in order
+/// and the last matching rule wins, so a later `exclude = false` rule can
+/// carve an exception back out of an earlier exclude. With no rules
+/// configured, everything is logged. This only ever applies to
+/// `Event::I3`, so `Event::Flush` (always `Priority::Urgent`) is never at
+/// risk of being filtered.
+pub struct Filterer {
+    rules: Vec<(GlobMatcher, bool)>,
+}
+
+impl Filterer {
+    pub fn new(rules: &[FilterRule]) -> Result<Self, TrackErr> {
+        let rules = rules
+            .iter()
+            .map(|r| {
+                Glob::new(&r.pattern)
+                    .map(|g| (g.compile_matcher(), r.exclude))
+                    .map_err(TrackErr::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Filterer { rules })
+    }
+
+    /// Should `log` be dropped before it reaches the write path?
+    pub fn excludes(&self, log: &I3Log) -> bool {
+        let mut excluded = false;
+        for (matcher, exclude) in &self.rules {
+            if matcher.is_match(&log.window_class)
+                || matcher.is_match(&log.window_title)
+                || matcher.is_match(&log.workspace)
+            {
+                excluded = *exclude;
+            }
+        }
+        excluded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(workspace: &str, class: &str, title: &str) -> I3Log {
+        I3Log::new(workspace.to_string(), class.to_string(), title.to_string())
+    }
+
+    #[test]
+    fn no_rules_logs_everything() {
+        let filterer = Filterer::new(&[]).unwrap();
+        assert!(!filterer.excludes(&log("1", "Firefox", "example.com")));
+    }
+
+    #[test]
+    fn exclude_rule_drops_matching_class() {
+        let filterer = Filterer::new(&[FilterRule {
+            pattern: "KeePassXC".to_string(),
+            exclude: true,
+        }])
+        .unwrap();
+        assert!(filterer.excludes(&log("1", "KeePassXC", "Database")));
+        assert!(!filterer.excludes(&log("1", "Firefox", "example.com")));
+    }
+
+    #[test]
+    fn later_include_rule_wins_over_earlier_exclude() {
+        let filterer = Filterer::new(&[
+            FilterRule {
+                pattern: "Firefox".to_string(),
+                exclude: true,
+            },
+            FilterRule {
+                pattern: "*work*".to_string(),
+                exclude: false,
+            },
+        ])
+        .unwrap();
+        assert!(filterer.excludes(&log("1", "Firefox", "cat videos")));
+        assert!(!filterer.excludes(&log("1", "Firefox", "work tracker")));
+    }
+
+    #[test]
+    fn rule_matches_workspace_name() {
+        let filterer = Filterer::new(&[FilterRule {
+            pattern: "scratch".to_string(),
+            exclude: true,
+        }])
+        .unwrap();
+        assert!(filterer.excludes(&log("scratch", "Alacritty", "term")));
+        assert!(!filterer.excludes(&log("1", "Alacritty", "term")));
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::priority::Priority;
+
+/// A focus/workspace change as reported by i3, captured at the moment it
+/// started being the active window.
+#[derive(Debug, Clone)]
+pub struct I3Log {
+    pub workspace: String,
+    pub window_class: String,
+    pub window_title: String,
+    pub start: SystemTime,
+}
+
+impl I3Log {
+    pub fn new(workspace: String, window_class: String, window_title: String) -> Self {
+        I3Log {
+            workspace,
+            window_class,
+            window_title,
+            start: SystemTime::now(),
+        }
+    }
+
+    /// Restart the accumulation window for the same window/workspace, used
+    /// when a `Tick` fires while the focus hasn't changed.
+    pub fn new_start(&self) -> Self {
+        I3Log {
+            start: SystemTime::now(),
+            ..self.clone()
+        }
+    }
+}
+
+/// A single logged entry: how long `I3Log` was focused, from `start` until
+/// the point it was written (either a tick, a focus change, or shutdown).
+#[derive(Debug)]
+pub struct Log {
+    id: u32,
+    workspace: String,
+    window_class: String,
+    window_title: String,
+    start: SystemTime,
+    end: SystemTime,
+}
+
+impl Log {
+    pub fn new(id: u32, prev: &I3Log) -> Self {
+        Log {
+            id,
+            workspace: prev.workspace.clone(),
+            window_class: prev.window_class.clone(),
+            window_title: prev.window_title.clone(),
+            start: prev.start,
+            end: SystemTime::now(),
+        }
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let start = self
+            .start
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+            .as_secs();
+        let end = self
+            .end
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+            .as_secs();
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.id, start, end, self.workspace, self.window_class, self.window_title
+        )
+    }
+}
+
+/// An event flowing through the tracker's event loop.
+#[derive(Debug)]
+pub enum Event {
+    I3(I3Log),
+    Tick(u32),
+    Flush,
+    /// The config file at this path changed on disk and should be reloaded.
+    ReloadConfig(PathBuf),
+}
+
+impl Event {
+    /// The priority used to route this event: `Flush` is always `Urgent`
+    /// since signal-driven shutdown must never be delayed behind a backlog
+    /// of `I3`/`Tick` events.
+    pub fn priority(&self) -> Priority {
+        match self {
+            Event::Flush => Priority::Urgent,
+            Event::I3(_) | Event::Tick(_) | Event::ReloadConfig(_) => Priority::Normal,
+        }
+    }
+}
+
+/// Open (creating if necessary) the log file at `log_path` for appending.
+pub fn writer<P: AsRef<Path>>(log_path: P) -> io::Result<impl Write> {
+    OpenOptions::new().create(true).append(true).open(log_path)
+}
+
+/// Scan an existing log file for the highest id written so far, so a
+/// restarted tracker continues numbering instead of restarting at 0.
+pub fn initial_event_id<P: AsRef<Path>>(log_path: P) -> u32 {
+    let file = match File::open(log_path) {
+        Ok(f) => f,
+        Err(_) => return 0,
+    };
+    BufReader::new(file)
+        .lines()
+        .filter_map(Result::ok)
+        .filter_map(|line| line.split('\t').next().and_then(|id| id.parse().ok()))
+        .last()
+        .map(|id: u32| id + 1)
+        .unwrap_or(0)
+}
+
+/// Total seconds a `window_class` was focused, summed across every record
+/// in the log whose end time is at or after `since` (or the whole log, if
+/// `since` is `None`), sorted by descending total.
+///
+/// Backs the `TodaySummary`/`TopWindows` IPC queries so status bars and
+/// scripts don't have to parse the log file themselves.
+pub fn window_totals<P: AsRef<Path>>(
+    log_path: P,
+    since: Option<SystemTime>,
+) -> io::Result<Vec<(String, u64)>> {
+    let since = since
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let file = File::open(log_path)?;
+    let mut totals: HashMap<String, u64> = HashMap::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let mut fields = line.splitn(6, '\t');
+        let (start, end, window_class) = match (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) {
+            (Some(_id), Some(start), Some(end), Some(_workspace)) => (start, end, fields.next()),
+            _ => continue,
+        };
+        let window_class = match window_class {
+            Some(c) => c,
+            None => continue,
+        };
+        let (start, end) = match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Ok(s), Ok(e)) => (s, e),
+            _ => continue,
+        };
+        if end < since {
+            continue;
+        }
+        *totals.entry(window_class.to_string()).or_insert(0) += end.saturating_sub(start);
+    }
+
+    let mut totals: Vec<(String, u64)> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1));
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    fn write_log(name: &str, lines: &[&str]) -> std::path::PathBuf {
+        let path = env::temp_dir().join(name);
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn window_totals_sums_durations_per_class() {
+        let path = write_log(
+            "i3track-test-window-totals-sums.log",
+            &[
+                "0\t100\t130\t1\tFirefox\ttab a",
+                "1\t130\t140\t1\tAlacritty\tshell",
+                "2\t140\t170\t1\tFirefox\ttab b",
+            ],
+        );
+        let totals = window_totals(&path, None).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            totals,
+            vec![("Firefox".to_string(), 60), ("Alacritty".to_string(), 10)]
+        );
+    }
+
+    #[test]
+    fn window_totals_filters_by_since() {
+        let path = write_log(
+            "i3track-test-window-totals-since.log",
+            &[
+                "0\t0\t10\t1\tFirefox\told",
+                "1\t100\t110\t1\tFirefox\tnew",
+            ],
+        );
+        let since = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(50);
+        let totals = window_totals(&path, Some(since)).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(totals, vec![("Firefox".to_string(), 10)]);
+    }
+}
@@ -0,0 +1,27 @@
+use std::time::{Duration, Instant};
+
+use futures::{Future, Sink};
+use tokio::{runtime::current_thread::Handle, timer::Delay};
+
+use crate::{Event, EventTx};
+
+/// The tick timer. Unlike `I3`/`Signals` it isn't a free-running stream:
+/// the consumer re-arms it with the current event id and tick delay after
+/// every logged focus change and every tick it acts on, so a `Tick` only
+/// ever takes effect for whichever window is focused right now. A config
+/// reload does not re-arm it: a changed tick delay applies from the next
+/// tick armed after the reload, and a tick already pending keeps the delay
+/// it was armed with.
+pub struct Clock;
+
+impl Clock {
+    /// Spawn a single delayed `Event::Tick(id)` onto `handle`.
+    pub fn spawn_tick(tx: EventTx, handle: &Handle, id: u32, delay: Duration) {
+        let fut = Delay::new(Instant::now() + delay)
+            .map_err(|_| ())
+            .and_then(move |_| tx.send(Event::Tick(id)).map_err(|_| ()))
+            .map(|_| ());
+
+        handle.spawn(fut).expect("Spawn timeout failed");
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc as std_mpsc,
+    thread,
+    time::Duration,
+};
+
+use futures::{Future, Sink};
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::runtime::current_thread::Handle;
+
+use super::Input;
+use crate::{Config, Event, EventTx, TrackErr};
+
+/// Watches the config file on disk and emits `Event::ReloadConfig` whenever
+/// it changes, so settings can be hot-reloaded without restarting.
+pub struct ConfigWatcher {
+    pub path: PathBuf,
+}
+
+impl Input for ConfigWatcher {
+    fn spawn(self, tx: EventTx, _handle: &Handle) -> Result<(), TrackErr> {
+        let path = self.path;
+        // Start from a file holding the defaults if none exists yet.
+        Config::ensure_exists(&path)?;
+        let (watch_tx, watch_rx) = std_mpsc::channel();
+        let mut watcher: RecommendedWatcher = Watcher::new(watch_tx, Duration::from_secs(2))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        // Watch the parent directory rather than `path` itself: editors
+        // that save via temp-file-plus-rename (vim's default, among
+        // others) replace the inode at `path`, which drops a watch held
+        // directly on that inode and leaves hot-reload silently dead after
+        // the first external edit. Watching the directory survives the
+        // rename; events are filtered down to ones that touch `path`'s
+        // filename below.
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("."));
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+
+        thread::spawn(move || {
+            // Keep the watcher alive for the lifetime of this thread.
+            let _watcher = watcher;
+            for event in watch_rx {
+                let touches_config = match event {
+                    DebouncedEvent::Create(p)
+                    | DebouncedEvent::Write(p)
+                    | DebouncedEvent::Chmod(p)
+                    | DebouncedEvent::NoticeWrite(p) => p == path,
+                    DebouncedEvent::Rename(_, to) => to == path,
+                    _ => false,
+                };
+                if !touches_config {
+                    continue;
+                }
+                if tx
+                    .clone()
+                    .send(Event::ReloadConfig(path.clone()))
+                    .wait()
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use std::{io, thread};
+
+use futures::{Future, Sink};
+use i3ipc::{
+    event::{
+        inner::{WindowChange, WorkspaceChange},
+        Event as I3Event, WindowEventInfo,
+    },
+    I3EventListener, Subscription,
+};
+use tokio::runtime::current_thread::Handle;
+
+use super::Input;
+use crate::{i3log::I3Log, Event, EventTx, TrackErr};
+
+/// Connects to i3's IPC socket and forwards window focus changes as
+/// `Event::I3`.
+///
+/// `i3ipc`'s listener is a blocking iterator, so it runs on its own thread
+/// and forwards into the shared channel rather than being driven by the
+/// tokio reactor directly.
+pub struct I3;
+
+impl Input for I3 {
+    fn spawn(self, tx: EventTx, _handle: &Handle) -> Result<(), TrackErr> {
+        let mut listener =
+            I3EventListener::connect().map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        listener
+            .subscribe(&[Subscription::Window, Subscription::Workspace])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        thread::spawn(move || {
+            // `Container` on a window-focus event doesn't carry the
+            // workspace it lives on, so we track the currently focused
+            // workspace separately from `WorkspaceEvent`s and stitch it
+            // into the next window focus change.
+            let mut workspace = String::new();
+
+            for event in listener.listen() {
+                let event = match event {
+                    Ok(e) => e,
+                    Err(e) => {
+                        error!("i3 listener error: {:?}", e);
+                        continue;
+                    }
+                };
+                match event {
+                    I3Event::WorkspaceEvent(w) => {
+                        // i3 also emits `WorkspaceEvent`s for workspaces
+                        // the user isn't on (e.g. `Urgent`/`Rename`/`Empty`
+                        // on a background workspace), where `current` is
+                        // that other workspace, not the focused one. Only
+                        // `Focus` tells us the user actually switched.
+                        if w.change == WorkspaceChange::Focus {
+                            if let Some(current) = w.current {
+                                workspace = current.name.unwrap_or_default();
+                            }
+                        }
+                    }
+                    I3Event::WindowEvent(w) => {
+                        if let Some(log) = to_i3log(w, &workspace) {
+                            if tx.clone().send(Event::I3(log)).wait().is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+/// Turn an i3 window-focus event into an `I3Log`, ignoring anything that
+/// isn't a focus change. `workspace` is the currently focused workspace,
+/// tracked separately from `WorkspaceEvent`s.
+fn to_i3log(event: WindowEventInfo, workspace: &str) -> Option<I3Log> {
+    if event.change != WindowChange::Focus {
+        return None;
+    }
+    let container = event.container;
+    let class = container
+        .window_properties
+        .as_ref()
+        .and_then(|p| p.get(&i3ipc::event::inner::WindowProperty::Class))
+        .cloned()
+        .unwrap_or_default();
+    let title = container.name.clone().unwrap_or_default();
+    Some(I3Log::new(workspace.to_string(), class, title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use i3ipc::event::inner::{Container, WindowChange, WindowEventInfo};
+
+    fn window_event(change: WindowChange, title: &str) -> WindowEventInfo {
+        WindowEventInfo {
+            change,
+            container: Container {
+                name: Some(title.to_string()),
+                ..Container::default()
+            },
+        }
+    }
+
+    #[test]
+    fn to_i3log_keeps_workspace_distinct_from_title() {
+        let event = window_event(WindowChange::Focus, "tab a");
+        let log = to_i3log(event, "1").unwrap();
+        assert_eq!(log.workspace, "1");
+        assert_eq!(log.window_title, "tab a");
+    }
+
+    #[test]
+    fn to_i3log_ignores_non_focus_changes() {
+        let event = window_event(WindowChange::Title, "tab a");
+        assert!(to_i3log(event, "1").is_none());
+    }
+}
@@ -0,0 +1,23 @@
+mod clock;
+mod config_watcher;
+mod i3;
+mod signals;
+
+pub use self::{clock::Clock, config_watcher::ConfigWatcher, i3::I3, signals::Signals};
+
+use crate::{EventTx, TrackErr};
+use tokio::runtime::current_thread::Handle;
+
+/// A producer of `Event`s that runs for the lifetime of the tracker.
+///
+/// Mirrors nbsh's `inputs` module: each implementor owns whatever
+/// connection/timer state it needs and, once spawned, pushes `Event`s into
+/// a shared `EventTx` until the runtime shuts down. This gives future
+/// triggers (idle detection, config reload, ...) a single place to plug in,
+/// and means every input goes through the same priority routing rather
+/// than picking a channel by hand.
+pub trait Input {
+    /// Start this input, either as a future on `handle` or on a thread of
+    /// its own, wiring it to send events into `tx`.
+    fn spawn(self, tx: EventTx, handle: &Handle) -> Result<(), TrackErr>;
+}
@@ -0,0 +1,39 @@
+use std::io;
+
+use futures::{Future, Sink, Stream};
+use tokio::runtime::current_thread::Handle;
+
+use super::Input;
+use crate::{Event, EventTx, TrackErr};
+
+/// Watches for SIGINT and SIGTERM, mapping both to `Event::Flush`.
+///
+/// `Event::Flush` is always `Priority::Urgent` (see `Event::priority`), so
+/// `EventTx` routes it onto the urgent channel on its own; shutdown is
+/// never delayed behind a backlog of `I3`/`Tick` events.
+pub struct Signals;
+
+impl Input for Signals {
+    fn spawn(self, tx: EventTx, handle: &Handle) -> Result<(), TrackErr> {
+        let sigint = tokio_signal::ctrl_c().flatten_stream().map(|_| ());
+        let sigterm = tokio_signal::unix::Signal::new(tokio_signal::unix::SIGTERM)
+            .flatten_stream()
+            .map(|_| ());
+
+        let fut = sigint
+            .select(sigterm)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            .for_each(move |_| {
+                tx.clone()
+                    .send(Event::Flush)
+                    .map(|_| ())
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            })
+            .map_err(|_| ());
+
+        handle
+            .spawn(fut)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        Ok(())
+    }
+}
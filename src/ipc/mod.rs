@@ -0,0 +1,7 @@
+mod request;
+mod server;
+
+pub use self::{
+    request::{ActivitySnapshot, Request, Response, WindowTotal},
+    server::{ActivityState, IpcServer},
+};
@@ -0,0 +1,48 @@
+use std::time::SystemTime;
+
+use crate::i3log::I3Log;
+
+/// A query sent over the IPC socket by a status bar or script.
+#[derive(Debug, Deserialize)]
+pub enum Request {
+    /// What window/workspace is focused right now.
+    CurrentActivity,
+    /// Per-window totals for everything logged since `since`.
+    TodaySummary { since: SystemTime },
+    /// The `n` most-focused windows across the whole log.
+    TopWindows { n: usize },
+}
+
+/// The answer to a `Request`.
+#[derive(Debug, Serialize)]
+pub enum Response {
+    CurrentActivity(Option<ActivitySnapshot>),
+    TodaySummary(Vec<WindowTotal>),
+    TopWindows(Vec<WindowTotal>),
+    Error(String),
+}
+
+/// A snapshot of the currently-focused window, for `Request::CurrentActivity`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivitySnapshot {
+    pub workspace: String,
+    pub window_class: String,
+    pub window_title: String,
+}
+
+impl<'a> From<&'a I3Log> for ActivitySnapshot {
+    fn from(log: &'a I3Log) -> Self {
+        ActivitySnapshot {
+            workspace: log.workspace.clone(),
+            window_class: log.window_class.clone(),
+            window_title: log.window_title.clone(),
+        }
+    }
+}
+
+/// Total time a window class was focused, in seconds.
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowTotal {
+    pub window_class: String,
+    pub seconds: u64,
+}
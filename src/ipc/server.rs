@@ -0,0 +1,112 @@
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crate::i3log::{self, I3Log};
+
+use super::request::{ActivitySnapshot, Request, Response, WindowTotal};
+
+/// Shared read-only view of the tracker's current state, kept up to date
+/// by the consumer loop so IPC queries never have to go through the event
+/// channel. `log_path` is behind a `Mutex` too since a config reload can
+/// rotate the tracker onto a different log file at runtime.
+#[derive(Clone)]
+pub struct ActivityState {
+    pub current: Arc<Mutex<Option<I3Log>>>,
+    pub log_path: Arc<Mutex<PathBuf>>,
+}
+
+/// Serves `Request`/`Response` queries over a Unix domain socket so status
+/// bars and scripts can poll "what am I doing right now" or "time per
+/// window today" instead of tailing the log file.
+///
+/// Modeled on distant's request/response process handler: each connection
+/// reads one newline-delimited JSON `Request` and writes back one
+/// newline-delimited JSON `Response`. Like `inputs::I3`, the listener runs
+/// on its own thread rather than the tokio reactor, since answering a
+/// query only ever reads shared state instead of feeding the event
+/// channel.
+pub struct IpcServer {
+    pub socket_path: PathBuf,
+    pub state: ActivityState,
+}
+
+impl IpcServer {
+    pub fn spawn(self) -> io::Result<()> {
+        let _ = fs::remove_file(&self.socket_path);
+        let listener = UnixListener::bind(&self.socket_path)?;
+        let state = self.state;
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let state = state.clone();
+                        thread::spawn(move || {
+                            if let Err(e) = handle_conn(stream, &state) {
+                                error!("ipc connection error: {:?}", e);
+                            }
+                        });
+                    }
+                    Err(e) => error!("ipc accept error: {:?}", e),
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+fn handle_conn(mut stream: UnixStream, state: &ActivityState) -> io::Result<()> {
+    let mut line = String::new();
+    BufReader::new(stream.try_clone()?).read_line(&mut line)?;
+
+    let response = match serde_json::from_str::<Request>(line.trim()) {
+        Ok(request) => handle_request(request, state),
+        Err(e) => Response::Error(format!("invalid request: {}", e)),
+    };
+
+    let payload =
+        serde_json::to_string(&response).unwrap_or_else(|e| format!("invalid response: {}", e));
+    writeln!(stream, "{}", payload)
+}
+
+fn handle_request(request: Request, state: &ActivityState) -> Response {
+    match request {
+        Request::CurrentActivity => {
+            let current = state.current.lock().expect("activity lock poisoned");
+            Response::CurrentActivity(current.as_ref().map(ActivitySnapshot::from))
+        }
+        Request::TodaySummary { since } => {
+            let log_path = state.log_path.lock().expect("log path lock poisoned").clone();
+            match i3log::window_totals(&log_path, Some(since)) {
+                Ok(totals) => Response::TodaySummary(to_window_totals(totals)),
+                Err(e) => Response::Error(e.to_string()),
+            }
+        }
+        Request::TopWindows { n } => {
+            let log_path = state.log_path.lock().expect("log path lock poisoned").clone();
+            match i3log::window_totals(&log_path, None) {
+                Ok(totals) => {
+                    Response::TopWindows(to_window_totals(totals).into_iter().take(n).collect())
+                }
+                Err(e) => Response::Error(e.to_string()),
+            }
+        }
+    }
+}
+
+fn to_window_totals(totals: Vec<(String, u64)>) -> Vec<WindowTotal> {
+    totals
+        .into_iter()
+        .map(|(window_class, seconds)| WindowTotal {
+            window_class,
+            seconds,
+        })
+        .collect()
+}
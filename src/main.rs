@@ -3,57 +3,108 @@ extern crate serde_derive;
 #[macro_use]
 extern crate log;
 
+mod config;
 mod error;
-mod i3;
+mod event_tx;
+mod filter;
 mod i3log;
+mod inputs;
+mod ipc;
+mod priority;
 
 pub(crate) use crate::{
+    config::Config,
     error::TrackErr,
+    event_tx::EventTx,
+    filter::Filterer,
     i3log::{Event, I3Log, Log},
+    inputs::{Clock, ConfigWatcher, Input, Signals, I3},
+    ipc::{ActivityState, IpcServer},
+    priority::Priority,
 };
-use futures::{
-    prelude::*,
-    sync::mpsc::{self, Sender},
-};
+use futures::{prelude::*, sync::mpsc, Async, Poll};
 use std::{
-    fs, io,
-    path::Path,
-    time::{Duration, Instant},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
-use tokio::{runtime::current_thread::Handle, timer::Delay};
+use tokio::runtime::current_thread::Handle;
 
-const TIMEOUT_DELAY: u64 = 10;
-const LOG_LIMIT: usize = 10;
-const LOG_BASE_NAME: &str = "i3tracker";
+/// Fixed application name, used to resolve the XDG config/data dirs. Unlike
+/// `Config::log_base_name` this can't itself live in the config file, since
+/// it's needed to find the config file in the first place.
+pub(crate) const APP_NAME: &str = "i3tracker";
 
 fn main() -> Result<(), TrackErr> {
     env_logger::init();
-    let log_path = setup_log()?;
+    let config_path = Config::path()?;
+    let mut config = Config::load(&config_path)?;
+    let mut log_path = setup_log(&config)?;
     // log interval
     info!("Creating listen channel");
     let (tx, rx) = mpsc::channel(50);
+    // Signal-driven shutdown gets its own small, always-served channel so it
+    // can never be stuck behind a burst of i3 events filling `rx`. Nothing
+    // sends on these directly: every producer gets the same `EventTx`,
+    // which routes each event by `Event::priority()`.
+    let (urgent_tx, urgent_rx) = mpsc::channel(8);
+    let event_tx = EventTx::new(urgent_tx, tx);
     let mut next_id = i3log::initial_event_id(&log_path);
     info!("Next id from logs is {:?}", next_id);
 
     // catch exit & write to log
     let mut rt = tokio::runtime::current_thread::Runtime::new().expect("Failed building runtime");
-    rt.spawn(sigint(tx.clone()));
+    if let Err(e) = Signals.spawn(event_tx.clone(), &rt.handle()) {
+        error!("{:?}", e);
+    }
 
-    // spawn listen loop
-    {
-        let tx = tx.clone();
-        if let Err(e) = i3::listen_loop(tx, rt.handle()) {
-            error!("{:?}", e);
-        }
+    // spawn i3 listener
+    if let Err(e) = I3.spawn(event_tx.clone(), &rt.handle()) {
+        error!("{:?}", e);
+    }
+
+    // watch the config file for live reloads
+    let config_watcher = ConfigWatcher {
+        path: config_path.clone(),
+    };
+    if let Err(e) = config_watcher.spawn(event_tx.clone(), &rt.handle()) {
+        error!("{:?}", e);
+    }
+
+    // serve live activity queries over a Unix socket
+    let current_activity: Arc<Mutex<Option<I3Log>>> = Arc::new(Mutex::new(None));
+    let shared_log_path = Arc::new(Mutex::new(log_path.clone()));
+    let socket_path = xdg::BaseDirectories::with_prefix(APP_NAME)?
+        .place_runtime_file(format!("{}.sock", APP_NAME))?;
+    let ipc_server = IpcServer {
+        socket_path,
+        state: ActivityState {
+            current: current_activity.clone(),
+            log_path: shared_log_path.clone(),
+        },
+    };
+    if let Err(e) = ipc_server.spawn() {
+        error!("{:?}", e);
     }
+
     let mut writer = i3log::writer(&log_path)?;
     let mut prev_i3log: Option<I3Log> = None;
+    let mut filterer = Filterer::new(&config.filters)?;
     // consume events
     let handle: Handle = rt.handle();
 
-    let f2 = rx.for_each(move |event| {
+    let events = EventStream {
+        urgent: urgent_rx,
+        normal: rx,
+    };
+
+    let f2 = events.for_each(move |event| {
         match event {
             Event::I3(e) => {
+                if filterer.excludes(&e) {
+                    return Ok(());
+                }
+
                 if let Some(ref prev) = prev_i3log {
                     Log::new(next_id, prev)
                         .write(&mut writer)
@@ -61,9 +112,8 @@ fn main() -> Result<(), TrackErr> {
                     next_id += 1;
                 }
 
-                handle
-                    .spawn(timeout(tx.clone(), next_id))
-                    .expect("Spawn timeout failed");
+                Clock::spawn_tick(event_tx.clone(), &handle, next_id, config.tick_delay());
+                *current_activity.lock().expect("activity lock poisoned") = Some(e.clone());
                 prev_i3log = Some(e);
             }
             Event::Tick(id) => {
@@ -77,10 +127,9 @@ fn main() -> Result<(), TrackErr> {
                         .expect("write failed!");
                     next_id += 1;
                     prev_i3log = Some(prev.new_start());
+                    *current_activity.lock().expect("activity lock poisoned") = prev_i3log.clone();
                 }
-                handle
-                    .spawn(timeout(tx.clone(), next_id))
-                    .expect("Spawn timeout failed");
+                Clock::spawn_tick(event_tx.clone(), &handle, next_id, config.tick_delay());
             }
             Event::Flush => {
                 if let Some(ref prev) = prev_i3log {
@@ -90,6 +139,50 @@ fn main() -> Result<(), TrackErr> {
                 }
                 std::process::exit(0);
             }
+            Event::ReloadConfig(path) => match Config::load(&path).and_then(|c| {
+                let f = Filterer::new(&c.filters)?;
+                Ok((c, f))
+            }) {
+                Ok((new_config, new_filterer)) => {
+                    info!("Reloaded config: {:?}", new_config);
+                    // `rotate()` picks the next log file from the data
+                    // dir's current file count, so it's only safe to call
+                    // again when the settings it depends on actually
+                    // changed — otherwise every reload (e.g. just editing
+                    // `tick_delay_secs` or `filters`) would rotate onto a
+                    // fresh file and fragment history across a growing
+                    // pile of near-empty logs.
+                    if new_config.log_limit != config.log_limit
+                        || new_config.log_base_name != config.log_base_name
+                    {
+                        match setup_log(&new_config) {
+                            Ok(new_log_path) if new_log_path != log_path => {
+                                match i3log::writer(&new_log_path) {
+                                    Ok(new_writer) => {
+                                        info!("Rotated log to {:?}", new_log_path);
+                                        writer = new_writer;
+                                        log_path = new_log_path.clone();
+                                        *shared_log_path
+                                            .lock()
+                                            .expect("log path lock poisoned") = new_log_path;
+                                    }
+                                    Err(e) => error!(
+                                        "Failed to reopen log after config reload: {:?}",
+                                        e
+                                    ),
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                error!("Failed to rotate log after config reload: {:?}", e)
+                            }
+                        }
+                    }
+                    config = new_config;
+                    filterer = new_filterer;
+                }
+                Err(e) => error!("Invalid config, keeping previous: {:?}", e),
+            },
         }
         Ok(())
     });
@@ -98,38 +191,39 @@ fn main() -> Result<(), TrackErr> {
     Ok(())
 }
 
-fn timeout(tx: Sender<Event>, id: u32) -> impl Future<Item = (), Error = ()> {
-    Delay::new(Instant::now() + Duration::from_secs(TIMEOUT_DELAY))
-        .map_err(|_| ())
-        .and_then(move |_| tx.send(Event::Tick(id)).map_err(|_| ()))
-        .map(|_| ())
-        .map_err(|_| ())
+/// Merges the urgent and normal event sources into a single stream,
+/// draining `urgent` first on every poll so a backlog on `normal` can never
+/// delay an `Urgent` event such as `Event::Flush`.
+struct EventStream {
+    urgent: mpsc::Receiver<Event>,
+    normal: mpsc::Receiver<Event>,
 }
 
-fn sigint(tx: Sender<Event>) -> impl Future<Item = (), Error = ()> {
-    tokio_signal::ctrl_c()
-        .flatten_stream()
-        .for_each(move |_| {
-            tx.clone()
-                .send(Event::Flush)
-                .map(|_| ())
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
-        })
-        .map_err(|_| ())
+impl Stream for EventStream {
+    type Item = Event;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<Event>, ()> {
+        match self.urgent.poll()? {
+            Async::Ready(Some(event)) => return Ok(Async::Ready(Some(event))),
+            Async::Ready(None) | Async::NotReady => {}
+        }
+        self.normal.poll()
+    }
 }
 
-fn setup_log() -> Result<impl AsRef<Path>, TrackErr> {
+fn setup_log(config: &Config) -> Result<PathBuf, TrackErr> {
     // get data dir
-    let xdg_dir = xdg::BaseDirectories::with_prefix(LOG_BASE_NAME)?;
+    let xdg_dir = xdg::BaseDirectories::with_prefix(APP_NAME)?;
     let home = xdg_dir.get_data_home();
     info!("Setting up log in {:?}", home.as_path());
-    let cur_log = rotate(home.as_path(), LOG_LIMIT)?;
+    let cur_log = rotate(home.as_path(), config.log_limit, &config.log_base_name)?;
     info!("Current log is {:?}", cur_log);
 
-    Ok(xdg_dir.place_data_file(format!("{}{}.{}", LOG_BASE_NAME, ".log", cur_log))?)
+    Ok(xdg_dir.place_data_file(format!("{}{}.{}", config.log_base_name, ".log", cur_log))?)
 }
 
-fn rotate<P: AsRef<Path>>(dir: P, num: usize) -> Result<usize, TrackErr> {
+fn rotate<P: AsRef<Path>>(dir: P, num: usize, base_name: &str) -> Result<usize, TrackErr> {
     let mut files = Vec::new();
 
     for entry in fs::read_dir(dir)? {
@@ -139,7 +233,7 @@ fn rotate<P: AsRef<Path>>(dir: P, num: usize) -> Result<usize, TrackErr> {
             .file_stem()
             .map(|h| {
                 h.to_str()
-                    .map(|g| g.starts_with(LOG_BASE_NAME))
+                    .map(|g| g.starts_with(base_name))
                     .unwrap_or(false)
             })
             .unwrap_or(false);
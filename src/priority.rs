@@ -0,0 +1,11 @@
+/// Priority of an event, used to decide which delivery path it travels on.
+///
+/// Modeled on watchexec's priority scheme: `Urgent` events (signal-driven
+/// shutdown) must never sit behind a backlog of `Normal` events in a bounded
+/// channel, since that backlog is exactly what would make the tracker
+/// impossible to quit cleanly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    Normal,
+    Urgent,
+}